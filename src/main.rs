@@ -24,39 +24,31 @@ type Clue = [Info; 5];
 
 const A: u8 = 'a' as u8;
 
+// The minimum and maximum number of times a letter can occur in a consistent word.
+// `max` starts at 5 (unconstrained) and is pinned down to an exact count once a
+// Black clue is seen for a letter that also has Green/Yellow occurrences elsewhere
+// in the same guess, since real Wordle only marks the letters beyond the answer's
+// true count as Black.
 #[derive(PartialEq, Eq, Clone, Copy)]
-enum LetterStatus {
-    Yes, No, Unknown
+struct LetterCount {
+    min: u8,
+    max: u8,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
-struct LettersContained([LetterStatus; 26]);
+struct LetterCounts([LetterCount; 26]);
 
-impl LettersContained {
-    #[inline(always)]
-    fn possibly_contains(&self, x: u8) -> bool {
-        match self.0[u8_to_letter_index(x)] {
-            LetterStatus::No => false,
-            LetterStatus::Unknown | LetterStatus::Yes => true
-        }
-    }
-
-    #[inline(always)]
-    fn mark_contains(&mut self, x: u8) {
-        self.0[u8_to_letter_index(x)] = LetterStatus::Yes
-    }
-
-    #[inline(always)]
-    fn mark_not_contains(&mut self, x: u8) {
-        self.0[u8_to_letter_index(x)] = LetterStatus::No
-    }
-}
-
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 struct InfoState {
     // The non-alphabetic code point 0 is used for unknown spots.
     mask: [u8; 5],
-    letters: LettersContained
+    letters: LetterCounts,
+    // excluded[i] is a bitmask (bit = letter index) of letters a Yellow or Black clue
+    // has ruled out at position i specifically. The min/max counts alone aren't enough:
+    // a Yellow at position i means the letter is in the word but NOT at position i, so
+    // without this an anagram of a past guess (e.g. "trace" vs answer "react") can stay
+    // "consistent" forever.
+    excluded: [u32; 5],
 }
 
 #[inline(always)]
@@ -64,35 +56,91 @@ fn u8_to_letter_index(x: u8) -> usize {
     (x - A) as usize
 }
 
+fn letter_counts(w: &Word) -> [u8; 26] {
+    let mut counts = [0u8; 26];
+    for &c in w.iter() {
+        counts[u8_to_letter_index(c)] += 1;
+    }
+    counts
+}
+
+// Colors guess letters the way real Wordle does: Greens are matched first and
+// consumed from the answer's per-letter counts, then Yellows are assigned from
+// what counts remain, so a repeated guess letter only gets as many Yellows/Greens
+// as the answer actually has of that letter.
 fn clue(guess: Word, answer: Word) -> Clue {
-    array_init(|i| {
+    let mut remaining = letter_counts(&answer);
+    let mut result = [Info::Black, Info::Black, Info::Black, Info::Black, Info::Black];
+
+    for i in 0..5 {
         if guess[i] == answer[i] {
-            Info::Green
-        } else if answer.contains(& guess[i]) {
-            Info::Yellow
-        } else {
-            Info::Black
+            result[i] = Info::Green;
+            remaining[u8_to_letter_index(guess[i])] -= 1;
+        }
+    }
+    for i in 0..5 {
+        if guess[i] != answer[i] {
+            let idx = u8_to_letter_index(guess[i]);
+            if remaining[idx] > 0 {
+                result[i] = Info::Yellow;
+                remaining[idx] -= 1;
+            }
         }
-    })
+    }
+    result
 }
 
 impl InfoState {
     fn consistent(&self, w: &Word) -> bool {
         let mask = &self.mask;
         for i in 0..5 {
-            if mask[i] == 0 {
-                if ! self.letters.possibly_contains(w[i]) {
-                    return false;
-                }
-            } else {
+            if mask[i] != 0 {
                 if w[i] != mask[i] {
                     return false;
                 }
+            } else if self.excluded[i] & (1 << u8_to_letter_index(w[i])) != 0 {
+                return false;
+            }
+        }
+        let counts = letter_counts(w);
+        for idx in 0..26 {
+            let lc = self.letters.0[idx];
+            if counts[idx] < lc.min || counts[idx] > lc.max {
+                return false;
             }
         }
         return true;
     }
 
+    // Folds the per-letter Green/Yellow/Black counts a guess produced into the
+    // running min/max bounds for each letter.
+    fn apply_clue_counts(&self, guess: &Word, clue: &Clue) -> LetterCounts {
+        let mut shown = [0u8; 26];
+        let mut black = [0u8; 26];
+        for i in 0..5 {
+            let idx = u8_to_letter_index(guess[i]);
+            match clue[i] {
+                Info::Green | Info::Yellow => shown[idx] += 1,
+                Info::Black => black[idx] += 1,
+            }
+        }
+
+        let mut letters = self.letters;
+        for idx in 0..26 {
+            if shown[idx] == 0 && black[idx] == 0 {
+                continue;
+            }
+            let lc = &mut letters.0[idx];
+            if shown[idx] > lc.min {
+                lc.min = shown[idx];
+            }
+            if black[idx] > 0 {
+                lc.max = shown[idx];
+            }
+        }
+        letters
+    }
+
     fn update(&self, guess: Word, clue: Clue) -> Self {
         let mask = array_init(|i| {
             if self.mask[i] != 0 {
@@ -105,70 +153,326 @@ impl InfoState {
                 }
             }
         });
-        
-        let mut letters = self.letters;
-        for i in 0..5 {
-            match clue[i] {
-                Info::Green | Info::Yellow => letters.mark_contains(guess[i]),
-                Info::Black => letters.mark_not_contains(guess[i])
+
+        let excluded = array_init(|i| {
+            if clue[i] == Info::Green {
+                self.excluded[i]
+            } else {
+                self.excluded[i] | (1 << u8_to_letter_index(guess[i]))
             }
-        }
+        });
 
         InfoState {
-            letters,
-            mask
+            letters: self.apply_clue_counts(&guess, &clue),
+            mask,
+            excluded
         }
     }
 
     fn update_from_answer(&self, guess: &Word, answer: &Word) -> Self {
-        let mut letters = self.letters;
-        // Set to true all chars in the guess that are also in the answer
-        for i in 0..5 {
-            let c = guess[i];
-            let answer_has_c = c == answer[0] || c == answer[1] || c == answer[2] || c == answer[3] || c == answer[4];
-            if answer_has_c {
-                letters.mark_contains(c)
-            } else {
-                letters.mark_not_contains(c);
-            }
-        }
+        self.update(*guess, clue(*guess, *answer))
+    }
 
+    fn new() -> Self {
         InfoState {
-            letters,
-            mask: array_init(|i| {
-                if self.mask[i] != 0 {
-                    self.mask[i]
-                } else {
-                    if guess[i] == answer[i] {
-                        guess[i]
-                    } else {
-                        0
-                    }
-                }
-            })
+            letters: LetterCounts(array_init(|_| LetterCount { min: 0, max: 5 })),
+            mask: array_init(|_| 0),
+            excluded: array_init(|_| 0),
         }
     }
+}
 
+type Word = [u8; 5];
 
+// Black=0, Yellow=1, Green=2, so a Clue can be packed into a base-3 integer in 0..243.
+const POWERS_OF_THREE: [u32; 5] = [1, 3, 9, 27, 81];
 
-    fn new() -> Self {
-        InfoState {
-            letters: LettersContained(array_init(|_| LetterStatus::Unknown)),
-            mask: array_init(|_| 0),
+fn info_digit(i: &Info) -> u32 {
+    match i {
+        Info::Black => 0,
+        Info::Yellow => 1,
+        Info::Green => 2,
+    }
+}
+
+fn clue_code(c: &Clue) -> usize {
+    POWERS_OF_THREE.iter().zip(c.iter()).map(|(p, i)| p * info_digit(i)).sum::<u32>() as usize
+}
+
+// Bucket `possible_answers` by the clue pattern `guess` would produce against each one.
+fn histogram_for_guess(guess: &Word, possible_answers: &Vec<Word>) -> [u32; 243] {
+    let mut histogram = [0u32; 243];
+    for answer in possible_answers {
+        histogram[clue_code(&clue(*guess, *answer))] += 1;
+    }
+    histogram
+}
+
+// Shannon entropy (in bits) of a clue-pattern histogram. Higher entropy means the guess
+// splits the remaining answers into more, more evenly-sized buckets.
+fn entropy_from_histogram(histogram: &[u32; 243], n: f64) -> f64 {
+    histogram.iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn guess_entropy(guess: &Word, possible_answers: &Vec<Word>) -> f64 {
+    entropy_from_histogram(&histogram_for_guess(guess, possible_answers), possible_answers.len() as f64)
+}
+
+// sum over hypothetical answers of the number of words that would remain consistent,
+// i.e. for each bucket of size k, every one of its k answers would leave k candidates.
+fn expected_remaining_from_histogram(histogram: &[u32; 243]) -> usize {
+    histogram.iter().map(|&count| (count as usize) * (count as usize)).sum()
+}
+
+fn unnormalized_expected_remaining_possibilities(guess: &Word, possible_answers: &Vec<Word>) -> usize {
+    expected_remaining_from_histogram(&histogram_for_guess(guess, possible_answers))
+}
+
+// Packs a 5-letter word into the low 40 bits of a u64, so it can be used as a cheap
+// hashable/comparable key instead of the 5-byte array.
+fn pack_word(w: &Word) -> u64 {
+    let mut packed: u64 = 0;
+    for i in 0..5 {
+        packed |= (w[i] as u64) << (8 * i);
+    }
+    packed
+}
+
+// A dense guess x answer response table over a fixed word set, so that scoring a guess
+// is a row scan of precomputed clue codes instead of recomputing `clue` for every
+// (guess, answer) pair on every call. Only worth building when the answer set is small
+// enough that the O(n^2) table fits comfortably in memory.
+const MATRIX_THRESHOLD: usize = 2000;
+
+struct ResponseMatrix {
+    n: usize,
+    index_of: std::collections::HashMap<u64, usize>,
+    // table[guess_idx * n + answer_idx] = clue_code(clue(words[guess_idx], words[answer_idx]))
+    table: Vec<u8>,
+}
+
+impl ResponseMatrix {
+    fn build(words: &Vec<Word>) -> Self {
+        let n = words.len();
+        let index_of = words.iter().enumerate().map(|(i, w)| (pack_word(w), i)).collect();
+        let table: Vec<u8> = (0..n).into_par_iter().flat_map(|gi| {
+            let guess = words[gi];
+            (0..n).map(|ai| clue_code(&clue(guess, words[ai])) as u8).collect::<Vec<u8>>()
+        }).collect();
+
+        ResponseMatrix { n, index_of, table }
+    }
+
+    fn index_of(&self, w: &Word) -> Option<usize> {
+        self.index_of.get(&pack_word(w)).copied()
+    }
+
+    fn histogram_for_row(&self, guess_idx: usize) -> [u32; 243] {
+        let row = &self.table[guess_idx * self.n..(guess_idx + 1) * self.n];
+        let mut histogram = [0u32; 243];
+        for &code in row {
+            histogram[code as usize] += 1;
         }
+        histogram
     }
 }
 
-type Word = [u8; 5];
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScoreMode {
+    // Minimize the expected number of remaining candidates after the guess.
+    ExpectedRemaining,
+    // Maximize the Shannon entropy of the clue-pattern distribution.
+    Entropy,
+}
 
-// Given a guess and the actual hidden answer, how many words would be eliminated
-fn remaining_possibilities(info: &InfoState, guess: &Word, actual_answer: &Word, possible_answers: &Vec<Word>) -> usize {
-    let info = info.update_from_answer(guess, actual_answer);
-    possible_answers.par_iter().filter(|a| info.consistent(a)).count()
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GuessMode {
+    // Guesses must themselves still be consistent with prior clues.
+    Hard,
+    // Guesses may be drawn from the whole dictionary, including words that are
+    // already ruled out as the answer, if they probe the remaining answers better.
+    Easy,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    // Read clues from stdin one guess at a time.
+    Interactive,
+    // Play the solver to completion against every word in the dictionary and report
+    // aggregate guess-count statistics.
+    Bench,
+}
+
+struct Config {
+    score_mode: ScoreMode,
+    guess_mode: GuessMode,
+    run_mode: RunMode,
+}
+
+fn config_from_args() -> Config {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    Config {
+        score_mode: if args.iter().any(|a| a == "entropy") { ScoreMode::Entropy } else { ScoreMode::ExpectedRemaining },
+        guess_mode: if args.iter().any(|a| a == "easy") { GuessMode::Easy } else { GuessMode::Hard },
+        run_mode: if args.iter().any(|a| a == "bench") { RunMode::Bench } else { RunMode::Interactive },
+    }
 }
 
-fn unnormalized_expected_remaining_possibilities(info: &InfoState, guess: &Word, possible_answers: &Vec<Word>) -> usize {
-    possible_answers.par_iter().map(|a| remaining_possibilities(info, guess, a, possible_answers)).sum()
+// A generous cap on turns so a pathological solver/answer combination can't loop forever;
+// any game that runs this long is counted as a failure in the benchmark histogram.
+const MAX_TURNS: usize = 20;
+
+// Among guesses tied for the best score, prefer one that's still a possible answer: a
+// probe word outside `possible_answers` can never win on the turn it's guessed, so in
+// easy mode a non-candidate that merely ties with (rather than beats) a candidate must
+// not be chosen over it, or the solver makes zero progress guessing it forever.
+fn best_tied_preferring_candidate<T: PartialEq + Copy>(scored: &[(Word, T)], best: T, possible_answers: &Vec<Word>) -> Word {
+    scored.iter()
+        .filter(|(_, s)| *s == best)
+        .map(|(w, _)| *w)
+        .find(|w| possible_answers.contains(w))
+        .unwrap_or_else(|| scored.iter().find(|(_, s)| *s == best).unwrap().0)
+}
+
+fn choose_next_guess(score_mode: ScoreMode, candidate_guesses: &Vec<Word>, possible_answers: &Vec<Word>) -> Word {
+    // Once a single answer remains, every candidate scores identically (no guess can
+    // split a set of size 1), so guess the answer itself rather than an arbitrarily
+    // tie-broken probe word that can't win even if it ties on score.
+    if possible_answers.len() == 1 {
+        return possible_answers[0];
+    }
+    match score_mode {
+        ScoreMode::ExpectedRemaining => {
+            let scored: Vec<(Word, usize)> = candidate_guesses.iter()
+                .map(|g| (*g, unnormalized_expected_remaining_possibilities(g, possible_answers)))
+                .collect();
+            let best = scored.iter().map(|(_, s)| *s).min().unwrap();
+            best_tied_preferring_candidate(&scored, best, possible_answers)
+        }
+        ScoreMode::Entropy => {
+            let scored: Vec<(Word, f64)> = candidate_guesses.iter()
+                .map(|g| (*g, guess_entropy(g, possible_answers)))
+                .collect();
+            let best = scored.iter().map(|(_, s)| *s).fold(f64::NEG_INFINITY, f64::max);
+            best_tied_preferring_candidate(&scored, best, possible_answers)
+        }
+    }
+}
+
+// Same as `choose_next_guess`, but builds the precomputed response matrix when the
+// answer set is small enough, for use in the interactive loop where a single such
+// matrix is built and scored once per turn.
+fn choose_next_guess_with_matrix(config: &Config, possible_answers: &Vec<Word>, words: &Vec<Word>) -> Word {
+    if possible_answers.len() == 1 {
+        return possible_answers[0];
+    }
+
+    let matrix = if possible_answers.len() <= MATRIX_THRESHOLD {
+        Some(ResponseMatrix::build(possible_answers))
+    } else {
+        None
+    };
+
+    let candidate_guesses: &Vec<Word> = match config.guess_mode {
+        GuessMode::Hard => possible_answers,
+        GuessMode::Easy => words,
+    };
+
+    match config.score_mode {
+        ScoreMode::ExpectedRemaining => {
+            let scored_guesses: Vec<(Word, usize)> = candidate_guesses.iter().map(|guess| {
+                let score = match matrix.as_ref().and_then(|m| m.index_of(guess).map(|gi| (m, gi))) {
+                    Some((m, gi)) => expected_remaining_from_histogram(&m.histogram_for_row(gi)),
+                    None => unnormalized_expected_remaining_possibilities(guess, possible_answers),
+                };
+                println!("score {} = {}", std::str::from_utf8(guess).unwrap(), score);
+                (*guess, score)
+            }).collect();
+
+            let best = scored_guesses.iter().map(|(_, s)| *s).min().unwrap();
+            best_tied_preferring_candidate(&scored_guesses, best, possible_answers)
+        }
+        ScoreMode::Entropy => {
+            let n = possible_answers.len() as f64;
+            let scored_guesses: Vec<(Word, f64)> = candidate_guesses.iter().map(|guess| {
+                let score = match matrix.as_ref().and_then(|m| m.index_of(guess).map(|gi| (m, gi))) {
+                    Some((m, gi)) => entropy_from_histogram(&m.histogram_for_row(gi), n),
+                    None => guess_entropy(guess, possible_answers),
+                };
+                println!("entropy {} = {}", std::str::from_utf8(guess).unwrap(), score);
+                (*guess, score)
+            }).collect();
+
+            let best = scored_guesses.iter().map(|(_, s)| *s).fold(f64::NEG_INFINITY, f64::max);
+            best_tied_preferring_candidate(&scored_guesses, best, possible_answers)
+        }
+    }
+}
+
+// Plays the solver against a known `answer`, feeding it clues via `update_from_answer`
+// instead of reading them from stdin, and returns the number of guesses taken to win
+// (or MAX_TURNS + 1 if it failed to solve within the cap).
+fn play_game(words: &Vec<Word>, answer: Word, opening: Word, config: &Config) -> usize {
+    let mut info = InfoState::new();
+    let mut possible_answers = words.clone();
+    let mut guess = opening;
+
+    for turn in 1..=MAX_TURNS {
+        info = info.update_from_answer(&guess, &answer);
+        possible_answers = possible_answers.iter().filter(|a| info.consistent(a)).map(|a| *a).collect();
+
+        if guess == answer {
+            return turn;
+        }
+
+        let candidate_guesses: &Vec<Word> = match config.guess_mode {
+            GuessMode::Hard => &possible_answers,
+            GuessMode::Easy => words,
+        };
+        guess = choose_next_guess(config.score_mode, candidate_guesses, &possible_answers);
+    }
+    MAX_TURNS + 1
+}
+
+// Plays the solver to completion against every word in `words` (in parallel) and prints
+// the mean guess count, the worst case, the number of failures beyond 6 guesses, and a
+// histogram of turns-to-solve, so strategies and opening words can be compared by
+// measured performance.
+fn run_benchmark(words: &Vec<Word>, opening: Word, config: &Config) {
+    let turn_counts: Vec<usize> = words.par_iter().map(|answer| play_game(words, *answer, opening, config)).collect();
+
+    let n = turn_counts.len();
+    let worst = *turn_counts.iter().max().unwrap();
+    // A capped-out game (MAX_TURNS + 1) is also a failure beyond 6, so this one
+    // threshold covers both; folding either kind into the mean would make the
+    // headline average meaningless for comparing strategies/openers.
+    let solved: Vec<usize> = turn_counts.iter().cloned().filter(|&t| t <= 6).collect();
+    let failures = n - solved.len();
+
+    let mut histogram: std::collections::BTreeMap<usize, usize> = std::collections::BTreeMap::new();
+    for &t in &turn_counts {
+        *histogram.entry(t).or_insert(0) += 1;
+    }
+
+    println!("opening: {}", std::str::from_utf8(&opening).unwrap());
+    println!("answers: {}", n);
+    if solved.is_empty() {
+        println!("mean guesses: no games solved within 6 guesses");
+    } else {
+        let mean = solved.iter().sum::<usize>() as f64 / solved.len() as f64;
+        println!("mean guesses (solved only, {} games): {:.3}", solved.len(), mean);
+    }
+    println!("worst case: {}", worst);
+    println!("failures (>6 guesses): {}", failures);
+    for (turns, count) in &histogram {
+        println!("{}: {}", turns, count);
+    }
 }
 
 fn main() {
@@ -187,6 +491,16 @@ fn main() {
         ws
     };
 
+    let config = config_from_args();
+
+    let best_initial_guess = "lares";
+    let opening: Word = array_init(|i| best_initial_guess.as_bytes()[i]);
+
+    if config.run_mode == RunMode::Bench {
+        run_benchmark(&words, opening, &config);
+        return;
+    }
+
     let mut info = InfoState::new();
     let mut possible_answers = words.clone();
 
@@ -194,8 +508,6 @@ fn main() {
 
     // println!("The answer is {}", std::str::from_utf8(&answer).unwrap());
 
-    let best_initial_guess = "lares";
-
     fn read_clue() -> Clue {
         let mut line = String::new();
         std::io::stdin().read_line(&mut line).unwrap(); // including '\n'    
@@ -203,22 +515,14 @@ fn main() {
         array_init(|i| Info::from_u8(line[i]))
     };
 
-    let mut last_guess: Word = array_init(|i| best_initial_guess.as_bytes()[i]);
+    let mut last_guess: Word = opening;
     loop {
         let clue = read_clue();
         info = info.update(last_guess, clue);
         possible_answers = possible_answers.par_iter().filter(|a| info.consistent(a)).map(|a| *a).collect();
 
-        // find the word which yields the largest reduction in the number of consistent words
-
-        let scored_guesses: Vec<(Word, usize)> = possible_answers.iter().map(|guess| {
-            let score = unnormalized_expected_remaining_possibilities(&info, guess, &possible_answers);
-            println!("score {} = {}", std::str::from_utf8(guess).unwrap(), score);
-            (*guess, score)
-        }).collect();
+        let next_guess = choose_next_guess_with_matrix(&config, &possible_answers, &words);
 
-        let next_guess = scored_guesses.par_iter().min_by_key(|(_, s)| *s).unwrap().0;
-        
         println!("guess: {}", std::str::from_utf8(&next_guess).unwrap());
         last_guess = next_guess;
 